@@ -33,6 +33,7 @@ pub fn join_lines(file: &File, range: TextRange) -> LocalEdit {
 
     let node = find_covering_node(file.syntax(), range);
     let mut edit = EditBuilder::new();
+    let mut cursor_position = None;
     for node in node.descendants() {
         let text = match node.leaf_text() {
             Some(text) => text,
@@ -46,22 +47,168 @@ pub fn join_lines(file: &File, range: TextRange) -> LocalEdit {
             let pos: TextUnit = (pos as u32).into();
             let off = node.range().start() + range.start() + pos;
             if !edit.invalidates_offset(off) {
-                remove_newline(&mut edit, node, text.as_str(), off);
+                if let Some(pos) = remove_newline(&mut edit, node, text.as_str(), off) {
+                    cursor_position = Some(pos);
+                }
             }
         }
     }
 
     LocalEdit {
         edit: edit.finish(),
-        cursor_position: None,
+        cursor_position,
+    }
+}
+
+pub fn split_line(file: &File, offset: TextUnit) -> Option<LocalEdit> {
+    let range = TextRange::offset_len(offset, 0.into());
+    let node = find_covering_node(file.syntax(), range);
+
+    // Prefer expanding a delimited construct (call args, tuple, struct literal
+    // or definition) that the cursor sits inside of. The search stops at the
+    // enclosing block, whose own braces are never split.
+    if let Some(list) = node.ancestors()
+        .take_while(|it| it.kind() != BLOCK)
+        .find(|it| open_delim(*it).is_some())
+    {
+        return split_delimited(file, list);
+    }
+    // Otherwise, if the cursor is inside a method chain, break each `.call()`
+    // onto its own continuation line.
+    if node.ancestors().any(is_chain) {
+        let chain = node.ancestors().filter(|it| is_chain(*it)).last().unwrap();
+        return split_chain(file, chain);
+    }
+    None
+}
+
+fn split_delimited(file: &File, list: SyntaxNodeRef) -> Option<LocalEdit> {
+    let base = line_indent(file, list).to_string();
+    let indent = format!("{}    ", base);
+
+    // Scan the list's own direct children: the opening and closing delimiters
+    // and the top-level separators sit here, while any nested delimiters (a
+    // generic argument list, an inner call, ...) stay buried inside the item
+    // nodes and never masquerade as separators.
+    let mut open_end = None;
+    let mut close_start = None;
+    let mut separators = Vec::new();
+    for child in list.children() {
+        match child.kind() {
+            L_PAREN | L_BRACK | L_CURLY if open_end.is_none() => {
+                open_end = Some(child.range().end());
+            }
+            R_PAREN | R_BRACK | R_CURLY => {
+                close_start = Some(child.range().start());
+                break;
+            }
+            COMMA => separators.push(child.range()),
+            _ => (),
+        }
+    }
+    let open_end = open_end?;
+    let close_start = close_start?;
+
+    let text = file.syntax().text();
+    let mut items = Vec::new();
+    let mut item_start = open_end;
+    for sep in &separators {
+        items.push(TextRange::from_to(item_start, sep.start()));
+        item_start = sep.end();
+    }
+    items.push(TextRange::from_to(item_start, close_start));
+
+    let mut buf = text.slice(list.range().start()..open_end).to_string();
+    for item in items {
+        let chunk = text.slice(item).to_string();
+        let chunk = chunk.trim();
+        if chunk.is_empty() {
+            continue;
+        }
+        buf += &format!("\n{}{},", indent, chunk);
+    }
+    // Leave the cursor on the (reindented) close-delimiter line. The original
+    // cursor sits inside the range we replace, so it cannot be recovered by
+    // `apply_to_offset`; place it explicitly instead.
+    buf += &format!("\n{}", base);
+    let cursor_position = list.range().start() + TextUnit::of_str(&buf);
+    buf += &text.slice(close_start..list.range().end()).to_string();
+
+    let mut edit = EditBuilder::new();
+    edit.replace(list.range(), buf);
+    Some(LocalEdit {
+        edit: edit.finish(),
+        cursor_position: Some(cursor_position),
+    })
+}
+
+fn split_chain(file: &File, chain: SyntaxNodeRef) -> Option<LocalEdit> {
+    let base = line_indent(file, chain).to_string();
+    let indent = format!("{}    ", base);
+
+    let inserted = format!("\n{}", indent);
+    let mut edit = EditBuilder::new();
+    let mut node = chain;
+    let mut outer_dot = None;
+    let mut breaks = 0u32;
+    loop {
+        match node.children().find(|it| it.kind() == DOT) {
+            Some(dot) => {
+                outer_dot.get_or_insert(dot.range().start());
+                edit.insert(dot.range().start(), inserted.clone());
+                breaks += 1;
+            }
+            None => break,
+        }
+        match node.children().next() {
+            Some(receiver) => node = receiver,
+            None => break,
+        }
     }
+    // Park the cursor on the outermost broken-out line. Every insertion lands
+    // at or before that dot, so it shifts right by the total inserted length.
+    let cursor_position = outer_dot? + TextUnit::from(breaks * inserted.len() as u32);
+    Some(LocalEdit {
+        edit: edit.finish(),
+        cursor_position: Some(cursor_position),
+    })
+}
+
+fn open_delim(node: SyntaxNodeRef) -> Option<SyntaxNodeRef> {
+    node.children()
+        .find(|it| match it.kind() {
+            L_PAREN | L_BRACK | L_CURLY => true,
+            _ => false,
+        })
+}
+
+fn is_chain(node: SyntaxNodeRef) -> bool {
+    node.children().any(|it| it.kind() == DOT)
+}
+
+fn line_indent<'a>(file: &'a File, node: SyntaxNodeRef) -> &'a str {
+    // Indentation of the nearest ancestor (or `node` itself) that begins its
+    // line. `node_indent` only accepts leaves, so the whitespace before each
+    // ancestor is looked up here directly.
+    node.ancestors()
+        .filter_map(|it| {
+            let ws = find_leaf_at_offset(file.syntax(), it.range().start()).left_biased()?;
+            if ws.kind() != WHITESPACE {
+                return None;
+            }
+            let text = ws.leaf_text()?;
+            let pos = text.as_str().rfind('\n')?;
+            Some(&text[pos + 1..])
+        })
+        .next()
+        .unwrap_or("")
 }
 
 pub fn on_enter(file: &File, offset: TextUnit) -> Option<LocalEdit> {
     let comment = find_leaf_at_offset(file.syntax(), offset).left_biased().and_then(|it| ast::Comment::cast(it))?;
 
     if let ast::CommentFlavor::Multiline = comment.flavor() {
-        return None;
+        return on_enter_in_block_comment(file, comment, offset);
     }
 
     let prefix = comment.prefix();
@@ -80,6 +227,39 @@ pub fn on_enter(file: &File, offset: TextUnit) -> Option<LocalEdit> {
     })
 }
 
+fn on_enter_in_block_comment(
+    file: &File,
+    comment: ast::Comment,
+    offset: TextUnit,
+) -> Option<LocalEdit> {
+    let comment_range = comment.syntax().range();
+    let prefix = comment.prefix();
+    // Bail out until the cursor is past the opening `/*`.
+    if offset < comment_range.start() + TextUnit::of_str(prefix) {
+        return None;
+    }
+
+    // Align the continuation `*` under the second char of the opening
+    // delimiter, i.e. one column past the comment's own indentation.
+    let indent = node_indent(file, comment.syntax())?;
+    let star = format!("\n{} * ", indent);
+    let cursor_position = offset + TextUnit::of_str(&star);
+
+    let mut inserted = star;
+    if offset == comment_range.end() - TextUnit::of_str("*/") {
+        // Enter was pressed right before the closing delimiter, so push the
+        // `*/` down onto its own aligned line.
+        inserted += &format!("\n{} ", indent);
+    }
+
+    let mut edit = EditBuilder::new();
+    edit.insert(offset, inserted);
+    Some(LocalEdit {
+        edit: edit.finish(),
+        cursor_position: Some(cursor_position),
+    })
+}
+
 fn node_indent<'a>(file: &'a File, node: SyntaxNodeRef) -> Option<&'a str> {
     let ws = match find_leaf_at_offset(file.syntax(), node.range().start()) {
         LeafAtOffset::Between(l, r) => {
@@ -100,6 +280,27 @@ fn node_indent<'a>(file: &'a File, node: SyntaxNodeRef) -> Option<&'a str> {
     Some(&text[pos..])
 }
 
+type CharHandler = fn(&File, TextUnit) -> Option<LocalEdit>;
+
+/// Per-character handlers, keyed by the character that was just typed. New
+/// handlers are plugged in here without touching `on_char_typed` or its
+/// callers.
+const CHAR_HANDLERS: &[(char, CharHandler)] = &[
+    ('=', on_eq_typed),
+    ('.', on_dot_typed),
+    ('{', on_lcurly_typed),
+    ('>', on_gt_typed),
+];
+
+pub fn on_char_typed(file: &File, offset: TextUnit, typed: char) -> Option<LocalEdit> {
+    for &(c, handler) in CHAR_HANDLERS {
+        if c == typed {
+            return handler(file, offset);
+        }
+    }
+    None
+}
+
 pub fn on_eq_typed(file: &File, offset: TextUnit) -> Option<LocalEdit> {
     let let_stmt: ast::LetStmt = find_node_at_offset(file.syntax(), offset)?;
     if let_stmt.has_semi() {
@@ -125,12 +326,123 @@ pub fn on_eq_typed(file: &File, offset: TextUnit) -> Option<LocalEdit> {
     })
 }
 
+/// Typing `.` at the start of a line inside a method chain reindents the line
+/// so the `.` aligns one level under the chain's receiver.
+fn on_dot_typed(file: &File, offset: TextUnit) -> Option<LocalEdit> {
+    let before = offset - TextUnit::of_char('.');
+    let ws = find_leaf_at_offset(file.syntax(), before).left_biased()?;
+    if ws.kind() != WHITESPACE {
+        return None;
+    }
+    let ws_text = ws.leaf_text()?;
+    // The `.` must be the first non-whitespace character on its line.
+    let newline = ws_text.as_str().rfind('\n')?;
+    let current_indent = &ws_text.as_str()[newline + 1..];
+
+    let dot = find_leaf_at_offset(file.syntax(), before).right_biased()?;
+    let chain = dot.ancestors().filter(|it| is_chain(*it)).last()?;
+    let target = format!("{}    ", line_indent(file, chain));
+    if current_indent == target {
+        return None;
+    }
+
+    let ws_start = ws.range().start() + TextUnit::from((newline + 1) as u32);
+    let mut edit = EditBuilder::new();
+    edit.replace(TextRange::from_to(ws_start, before), target);
+    Some(LocalEdit {
+        edit: edit.finish(),
+        cursor_position: None,
+    })
+}
+
+/// Typing `{` after a control-flow header auto-closes the block with a matching
+/// `}`, leaving the cursor between the braces.
+fn on_lcurly_typed(file: &File, offset: TextUnit) -> Option<LocalEdit> {
+    let l_curly = offset - TextUnit::of_char('{');
+    let brace = find_leaf_at_offset(file.syntax(), l_curly).right_biased()?;
+    if brace.kind() != L_CURLY {
+        return None;
+    }
+    // Only the flow construct's *own* body should auto-close, not any `{`
+    // (struct literal, nested block, ...) that merely happens to sit somewhere
+    // inside a flow body. Look at what this brace directly opens.
+    if !opens_flow_body(brace) {
+        return None;
+    }
+    // Don't double-insert: if the block already carries a matching `}` (e.g.
+    // typing `{` inside an already-closed `if x {}`), leave it alone.
+    if brace.parent().map_or(false, |p| p.children().any(|it| it.kind() == R_CURLY)) {
+        return None;
+    }
+    let mut edit = EditBuilder::new();
+    edit.insert(offset, "}".to_string());
+    Some(LocalEdit {
+        edit: edit.finish(),
+        cursor_position: Some(offset),
+    })
+}
+
+/// Typing `>` to close a generic/closing context types over a `>` that is
+/// already there rather than inserting a duplicate.
+fn on_gt_typed(file: &File, offset: TextUnit) -> Option<LocalEdit> {
+    let gt = offset - TextUnit::of_char('>');
+    let typed = find_leaf_at_offset(file.syntax(), gt).right_biased()?;
+    if typed.kind() != R_ANGLE {
+        return None;
+    }
+    // If another `>` sits immediately after the one just typed, type over it
+    // instead of leaving a duplicate closing angle.
+    let next = find_leaf_at_offset(file.syntax(), offset).right_biased()?;
+    if next.kind() != R_ANGLE || next.range().start() != offset {
+        return None;
+    }
+    let mut edit = EditBuilder::new();
+    edit.delete(next.range());
+    Some(LocalEdit {
+        edit: edit.finish(),
+        cursor_position: Some(offset),
+    })
+}
+
+fn is_flow_header(kind: SyntaxKind) -> bool {
+    match kind {
+        IF_EXPR | WHILE_EXPR | LOOP_EXPR | FOR_EXPR | MATCH_EXPR => true,
+        _ => false,
+    }
+}
+
+/// Does `brace` (an `L_CURLY`) open the body of a control-flow construct
+/// directly, rather than some nested block or literal inside that body?
+fn opens_flow_body(brace: SyntaxNodeRef) -> bool {
+    let parent = match brace.parent() {
+        Some(it) => it,
+        None => return false,
+    };
+    // `if`/`while`/`for`/`loop` bodies are a block that hangs directly off the
+    // flow expression itself.
+    if ast::Block::cast(parent).is_some() {
+        return parent
+            .parent()
+            .map_or(false, |it| is_flow_header(it.kind()));
+    }
+    // A `match` body is an arm list sitting directly under the match expression.
+    parent.parent().map_or(false, |it| it.kind() == MATCH_EXPR)
+}
+
 fn remove_newline(
     edit: &mut EditBuilder,
     node: SyntaxNodeRef,
     node_text: &str,
     offset: TextUnit,
-) {
+) -> Option<TextUnit> {
+    // A newline that lives *inside* a string literal must not be turned into
+    // whitespace, or the string content is corrupted. Only a trailing `\`
+    // line-continuation may be folded; an unescaped newline is significant
+    // string content, so leave it untouched rather than silently dropping it.
+    if node.kind() == STRING || node.kind() == RAW_STRING {
+        return join_string_continuation(edit, node, node_text, offset);
+    }
+
     if node.kind() != WHITESPACE || node_text.bytes().filter(|&b| b == b'\n').count() != 1 {
         // The node is either the first or the last in the file
         let suff = &node_text[TextRange::from_to(
@@ -143,7 +455,7 @@ fn remove_newline(
             TextRange::offset_len(offset, ((spaces + 1) as u32).into()),
             " ".to_string(),
         );
-        return;
+        return None;
     }
 
     // Special case that turns something like:
@@ -156,7 +468,7 @@ fn remove_newline(
     //
     // into `my_function(<some-expr>)`
     if join_single_expr_block(edit, node).is_some() {
-        return
+        return None;
     }
 
     // The node is between two other nodes
@@ -202,6 +514,36 @@ fn remove_newline(
             compute_ws(prev, next).to_string(),
         );
     }
+    None
+}
+
+fn join_string_continuation(
+    edit: &mut EditBuilder,
+    node: SyntaxNodeRef,
+    node_text: &str,
+    offset: TextUnit,
+) -> Option<TextUnit> {
+    let local = u32::from(offset - node.range().start()) as usize;
+    // Only a `\` right before the newline is a line-continuation escape. A bare
+    // newline is significant string content (the value contains `\n` plus the
+    // following indentation), so folding it would change the program's meaning;
+    // leave such literals alone.
+    if !node_text[..local].ends_with('\\') {
+        return None;
+    }
+    // Indentation on the continuation line is swallowed together with the
+    // backslash and newline so no stray whitespace ends up inside the string.
+    let suffix_ws = node_text[local + 1..]
+        .bytes()
+        .take_while(|&b| b == b' ' || b == b'\t')
+        .count();
+    let start = offset - TextUnit::of_char('\\');
+    edit.delete(TextRange::from_to(
+        start,
+        offset + TextUnit::of_char('\n') + TextUnit::from(suffix_ws as u32),
+    ));
+    // The two halves meet where the continuation was removed.
+    Some(start)
 }
 
 fn is_trailing_comma(left: SyntaxKind, right: SyntaxKind) -> bool {
@@ -313,6 +655,20 @@ fn foo() {
 }");
     }
 
+    #[test]
+    fn test_join_lines_string_literal() {
+        check_join_lines(r#"
+fn foo() {
+    let s = "hello \<|>
+        world";
+}
+"#, r#"
+fn foo() {
+    let s = "hello <|>world";
+}
+"#);
+    }
+
     fn check_join_lines_sel(before: &str, after: &str) {
         let (sel, before) = extract_range(before);
         let file = File::parse(&before);
@@ -378,6 +734,76 @@ pub fn handle_find_matching_brace() {
 }");
     }
 
+    fn check_split_line(before: &str, after: &str) {
+        check_action(before, after, |file, offset| split_line(file, offset))
+    }
+
+    #[test]
+    fn test_split_line_fn_args() {
+        check_split_line(r"
+fn foo() {
+    foo(<|>1, 2, 3)
+}
+", r"
+fn foo() {
+    foo(
+        1,
+        2,
+        3,
+    <|>)
+}
+");
+    }
+
+    #[test]
+    fn test_split_line_struct() {
+        check_split_line(r"
+fn foo() {
+    S { <|>a: 1, b: 2 }
+}
+", r"
+fn foo() {
+    S {
+        a: 1,
+        b: 2,
+    <|>}
+}
+");
+    }
+
+    #[test]
+    fn test_split_line_generic_arg() {
+        // A comma buried inside a generic argument list is not a top-level
+        // separator and must not be split on.
+        check_split_line(r"
+fn foo() {
+    foo(<|>Vec::<u32, String>::new(), 1)
+}
+", r"
+fn foo() {
+    foo(
+        Vec::<u32, String>::new(),
+        1,
+    <|>)
+}
+");
+    }
+
+    #[test]
+    fn test_split_line_dot_chain() {
+        check_split_line(r"
+fn foo() {
+    foo.bar()<|>.baz()
+}
+", r"
+fn foo() {
+    foo
+        .bar()
+        <|>.baz()
+}
+");
+    }
+
     #[test]
     fn test_on_eq_typed() {
         fn do_check(before: &str, after: &str) {
@@ -419,6 +845,105 @@ fn foo() {
         // ");
     }
 
+    #[test]
+    fn test_on_char_typed_dispatches_eq() {
+        let (offset, before) = extract_offset(r"
+fn foo() {
+    let foo =<|> 1 + 1
+}
+");
+        let file = File::parse(&before);
+        let actual = on_char_typed(&file, offset, '=').unwrap().edit.apply(&before);
+        assert_eq_text!(r"
+fn foo() {
+    let foo = 1 + 1;
+}
+", &actual);
+    }
+
+    #[test]
+    fn test_on_char_typed_unknown_char() {
+        let (offset, before) = extract_offset("fn foo<|>() {}");
+        let file = File::parse(&before);
+        assert!(on_char_typed(&file, offset, 'x').is_none());
+    }
+
+    fn check_on_char_typed(typed: char, before: &str, after: &str) {
+        let (offset, before) = extract_offset(before);
+        let file = File::parse(&before);
+        let result = on_char_typed(&file, offset, typed).unwrap();
+        let actual = result.edit.apply(&before);
+        assert_eq_text!(after, &actual);
+    }
+
+    fn check_on_char_typed_noop(typed: char, before: &str) {
+        let (offset, before) = extract_offset(before);
+        let file = File::parse(&before);
+        assert!(on_char_typed(&file, offset, typed).is_none());
+    }
+
+    #[test]
+    fn test_on_dot_typed() {
+        // A `.` that opens a continuation line is reindented one level under
+        // the receiver.
+        check_on_char_typed('.', r"
+fn foo() {
+    foo
+    .<|>bar()
+}
+", r"
+fn foo() {
+    foo
+        .bar()
+}
+");
+        // Already aligned: nothing to do.
+        check_on_char_typed_noop('.', r"
+fn foo() {
+    foo
+        .<|>bar()
+}
+");
+    }
+
+    #[test]
+    fn test_on_lcurly_typed() {
+        // A `{` after a flow header auto-closes the block.
+        check_on_char_typed('{', r"
+fn foo() {
+    if true {<|>
+", r"
+fn foo() {
+    if true {}
+");
+        // Don't double-insert when the block is already closed.
+        check_on_char_typed_noop('{', r"
+fn foo() {
+    if true {<|>}
+}
+");
+    }
+
+    #[test]
+    fn test_on_gt_typed() {
+        // Type over the `>` that already closes the generic.
+        check_on_char_typed('>', r"
+fn foo() {
+    let _: Foo<i32><|>>;
+}
+", r"
+fn foo() {
+    let _: Foo<i32>;
+}
+");
+        // No trailing `>` to type over: nothing to do.
+        check_on_char_typed_noop('>', r"
+fn foo() {
+    let _: Foo<i32><|>;
+}
+");
+    }
+
     #[test]
     fn test_on_enter() {
         fn apply_on_enter(before: &str) -> Option<String> {
@@ -460,6 +985,23 @@ impl S {
     /// <|> docs.
     fn foo() {}
 }
+");
+        do_check(r"
+/*<|>
+ */
+", r"
+/*
+ * <|>
+ */
+");
+        do_check(r"
+/*
+ * Some docs<|>*/
+", r"
+/*
+ * Some docs
+ * <|>
+ */
 ");
         do_check_noop(r"<|>//! docz");
     }